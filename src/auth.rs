@@ -0,0 +1,156 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+/// How long before actual expiry we proactively refresh, to avoid a request
+/// racing a token that lapses mid-flight.
+const REFRESH_SKEW: Duration = Duration::from_secs(30);
+
+/// Supplies the Shopify access token `delete_shopify_resource` sends on
+/// every call. Either a static `SHOP_ACCESS_TOKEN` (classic app installs,
+/// the token never changes) or an OAuth2 refresh-token flow that keeps
+/// itself fresh transparently.
+#[derive(Clone)]
+pub enum ShopifyTokenSource {
+    Static(String),
+    OAuth(Arc<Mutex<OAuthState>>),
+}
+
+pub struct OAuthState {
+    shop_url: String,
+    client_id: String,
+    client_secret: String,
+    refresh_token: String,
+    access_token: String,
+    issued_at: SystemTime,
+    expires_in: Duration,
+}
+
+impl ShopifyTokenSource {
+    /// Builds from env vars: OAuth when `SHOP_CLIENT_ID`/`SHOP_CLIENT_SECRET`/
+    /// `SHOP_REFRESH_TOKEN` are all set, falling back to the static
+    /// `SHOP_ACCESS_TOKEN` behavior otherwise.
+    pub async fn from_env(shop_url: &str) -> Self {
+        let oauth_vars = (
+            std::env::var("SHOP_CLIENT_ID"),
+            std::env::var("SHOP_CLIENT_SECRET"),
+            std::env::var("SHOP_REFRESH_TOKEN"),
+        );
+
+        match oauth_vars {
+            (Ok(client_id), Ok(client_secret), Ok(refresh_token)) => {
+                let mut state = OAuthState {
+                    shop_url: shop_url.to_string(),
+                    client_id,
+                    client_secret,
+                    refresh_token,
+                    access_token: String::new(),
+                    issued_at: SystemTime::UNIX_EPOCH,
+                    expires_in: Duration::ZERO,
+                };
+                state
+                    .refresh()
+                    .await
+                    .expect("initial Shopify OAuth2 token exchange failed");
+                ShopifyTokenSource::OAuth(Arc::new(Mutex::new(state)))
+            }
+            _ => {
+                let token = std::env::var("SHOP_ACCESS_TOKEN").expect("SHOP_ACCESS_TOKEN not set");
+                ShopifyTokenSource::Static(token)
+            }
+        }
+    }
+
+    /// Returns the current access token, refreshing it first if it has
+    /// lapsed (or is about to). Refresh failures are surfaced to the caller
+    /// instead of panicking, so a flaky token endpoint becomes an ordinary
+    /// [`crate::retry::DeleteError`] like any other transient failure.
+    pub async fn current_token(&self) -> Result<String, reqwest::Error> {
+        match self {
+            ShopifyTokenSource::Static(token) => Ok(token.clone()),
+            ShopifyTokenSource::OAuth(state) => {
+                let mut state = state.lock().await;
+                if state.expires_soon() {
+                    state.refresh().await?;
+                }
+                Ok(state.access_token.clone())
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+impl OAuthState {
+    fn expires_soon(&self) -> bool {
+        let elapsed = self.issued_at.elapsed().unwrap_or(Duration::MAX);
+        elapsed + REFRESH_SKEW >= self.expires_in
+    }
+
+    async fn refresh(&mut self) -> Result<(), reqwest::Error> {
+        let url = format!("{}/admin/oauth/access_token", self.shop_url);
+        let response: TokenResponse = reqwest::Client::new()
+            .post(&url)
+            .json(&serde_json::json!({
+                "client_id": self.client_id,
+                "client_secret": self.client_secret,
+                "refresh_token": self.refresh_token,
+                "grant_type": "refresh_token",
+            }))
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        self.access_token = response.access_token;
+        self.issued_at = SystemTime::now();
+        self.expires_in = Duration::from_secs(response.expires_in);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_issued(issued_at: SystemTime, expires_in: Duration) -> OAuthState {
+        OAuthState {
+            shop_url: String::new(),
+            client_id: String::new(),
+            client_secret: String::new(),
+            refresh_token: String::new(),
+            access_token: String::new(),
+            issued_at,
+            expires_in,
+        }
+    }
+
+    #[test]
+    fn not_expiring_soon_well_within_expiry() {
+        let expires_in = Duration::from_secs(600);
+        let state = state_issued(SystemTime::now(), expires_in);
+        assert!(!state.expires_soon());
+    }
+
+    #[test]
+    fn expiring_soon_inside_the_refresh_skew() {
+        let expires_in = Duration::from_secs(60);
+        let issued_at = SystemTime::now() - (expires_in - REFRESH_SKEW / 2);
+        let state = state_issued(issued_at, expires_in);
+        assert!(state.expires_soon());
+    }
+
+    #[test]
+    fn expiring_soon_once_already_lapsed() {
+        let expires_in = Duration::from_secs(60);
+        let issued_at = SystemTime::now() - (expires_in * 2);
+        let state = state_issued(issued_at, expires_in);
+        assert!(state.expires_soon());
+    }
+}
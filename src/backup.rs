@@ -0,0 +1,109 @@
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use crate::engine;
+use crate::resource::{self, ResourceSpec};
+use crate::{DataElement, StrapiConfig};
+
+fn strapi_config_from_env() -> StrapiConfig {
+    StrapiConfig {
+        base_url: std::env::var("STRAPI_BASE_URL").expect("STRAPI_BASE_URL not set"),
+        auth_token: std::env::var("STRAPI_TOKEN").expect("STRAPI_TOKEN not set"),
+    }
+}
+
+fn spec_for(resource_name: &str) -> ResourceSpec {
+    let specs = resource::load_specs(Path::new(resource::DEFAULT_CONFIG_PATH));
+    resource::find(&specs, resource_name)
+        .unwrap_or_else(|| panic!("no resource spec configured for '{}'", resource_name))
+        .clone()
+}
+
+/// Streams every `DataElement` across all pages of `resource_name` as
+/// newline-delimited JSON, so operators have something to diff and replay
+/// before running a destructive `--delete`.
+pub async fn export_resource(
+    resource_name: &str,
+    output: Option<PathBuf>,
+) -> Result<(), reqwest::Error> {
+    let mut writer: Box<dyn Write> = match &output {
+        Some(path) => {
+            Box::new(std::fs::File::create(path).expect("failed to create export file"))
+        }
+        None => Box::new(io::stdout()),
+    };
+
+    let spec = spec_for(resource_name);
+    let strapi_config = strapi_config_from_env();
+
+    let mut page = 1;
+    let mut exported = 0;
+
+    loop {
+        let root = engine::fetch_page(&strapi_config, &spec, page).await?;
+
+        for element in &root.data {
+            let line = serde_json::to_string(element).expect("DataElement always serializes");
+            writeln!(writer, "{}", line).expect("failed to write export line");
+            exported += 1;
+        }
+
+        if page >= root.meta.pagination.page_count {
+            break;
+        }
+        page += 1;
+    }
+
+    eprintln!("Exported {} {}", exported, resource_name);
+    Ok(())
+}
+
+/// Reads back NDJSON produced by [`export_resource`] (from a file, or
+/// stdin when no path is given) and recreates each entry in Strapi.
+pub async fn import_resource(resource_name: &str, input: Option<PathBuf>) {
+    let reader: Box<dyn BufRead> = match &input {
+        Some(path) => Box::new(io::BufReader::new(
+            std::fs::File::open(path).expect("failed to open import file"),
+        )),
+        None => Box::new(io::BufReader::new(io::stdin())),
+    };
+
+    let spec = spec_for(resource_name);
+    let strapi_config = strapi_config_from_env();
+    let client = reqwest::Client::new();
+
+    let mut restored = 0;
+    for line in reader.lines() {
+        let line = line.expect("failed to read import line");
+        if line.trim().is_empty() {
+            continue;
+        }
+        let element: DataElement =
+            serde_json::from_str(&line).expect("import line is not a valid DataElement");
+
+        let url = format!("{}/{}", strapi_config.base_url, spec.strapi_collection);
+        let body = serde_json::json!({ "data": element.attributes });
+        let response = client
+            .post(&url)
+            .header(
+                "Authorization",
+                format!("Bearer {}", strapi_config.auth_token),
+            )
+            .json(&body)
+            .send()
+            .await;
+
+        match response {
+            Ok(response) if response.status().is_success() => restored += 1,
+            Ok(response) => println!(
+                "Failed to restore {} {}: request rejected: {}",
+                resource_name,
+                element.id,
+                response.status()
+            ),
+            Err(e) => println!("Failed to restore {} {}: {}", resource_name, element.id, e),
+        }
+    }
+
+    println!("Restored {} {}", restored, resource_name);
+}
@@ -0,0 +1,279 @@
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+
+use crate::resource::ResourceSpec;
+use crate::retry::{call_with_retry, DeleteError};
+use crate::{DataElement, ProgressSink, Root, ShopifyConfig, StrapiConfig};
+
+fn create_filter(spec: &ResourceSpec, page: i32, page_size: i32) -> String {
+    let fields = spec
+        .strapi_fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| format!("fields[{}]={}", i, field))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    format!(
+        "{}&pagination[pageSize]={}&pagination[page]={}&publicationState=preview&locale[0]=en",
+        fields, page_size, page
+    )
+}
+
+pub async fn fetch_page(
+    strapi_config: &StrapiConfig,
+    spec: &ResourceSpec,
+    page: i32,
+) -> Result<Root, reqwest::Error> {
+    let url = format!(
+        "{}/{}?{}",
+        strapi_config.base_url,
+        spec.strapi_collection,
+        create_filter(spec, page, 10)
+    );
+
+    reqwest::Client::new()
+        .get(&url)
+        .header("Accept", "application/json")
+        .header(
+            "Authorization",
+            format!("Bearer {}", strapi_config.auth_token),
+        )
+        .send()
+        .await?
+        .json::<Root>()
+        .await
+}
+
+fn linked_shopify_id(spec: &ResourceSpec, data: &DataElement) -> Option<String> {
+    let field = spec.link_field.as_deref()?;
+    data.attributes.get(field)?.as_str().map(str::to_owned)
+}
+
+/// Bundles everything `process_page` needs besides the page itself, so
+/// adding another knob doesn't mean adding another positional parameter.
+struct DeleteContext<'a> {
+    shopify_config: &'a ShopifyConfig,
+    strapi_config: &'a StrapiConfig,
+    semaphore: &'a Semaphore,
+    max_retries: u32,
+}
+
+/// Kicks off `spec`'s deletion job: fetches page 1, then walks every page
+/// with bounded concurrency, deleting (or, in `dry_run`, just logging) each
+/// entry and its linked Shopify resource.
+#[tracing::instrument(name = "delete_job", skip(progress), fields(resource = %spec.name))]
+pub async fn run_job(
+    spec: ResourceSpec,
+    concurrency: usize,
+    max_retries: u32,
+    dry_run: bool,
+    progress: Option<Arc<dyn ProgressSink>>,
+) {
+    let (shopify_config, strapi_config) = crate::load_configs().await;
+    let shopify_config = Arc::new(shopify_config);
+    let strapi_config = Arc::new(strapi_config);
+
+    let first_page = match fetch_page(&strapi_config, &spec, 1).await {
+        Ok(root) => root,
+        Err(e) => {
+            tracing::error!(resource = %spec.name, error = %e, "failed to fetch first page");
+            if let Some(sink) = &progress {
+                sink.fail(&format!("failed to fetch first page: {}", e));
+            }
+            return;
+        }
+    };
+
+    let total_pages = first_page.meta.pagination.page_count;
+    let total = first_page.meta.pagination.total;
+    let mut processed = 0;
+
+    tracing::info!(resource = %spec.name, total, dry_run, "starting deletion run");
+    if let Some(sink) = &progress {
+        sink.update(0, total);
+    }
+
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let spec = Arc::new(spec);
+
+    let tasks: Vec<_> = (1..=total_pages)
+        .map(|page| {
+            let spec = Arc::clone(&spec);
+            let shopify_config = Arc::clone(&shopify_config);
+            let strapi_config = Arc::clone(&strapi_config);
+            let semaphore = Arc::clone(&semaphore);
+
+            tokio::spawn(async move {
+                match fetch_page(&strapi_config, &spec, page).await {
+                    Ok(root) => {
+                        let ctx = DeleteContext {
+                            shopify_config: &shopify_config,
+                            strapi_config: &strapi_config,
+                            semaphore: &semaphore,
+                            max_retries,
+                        };
+                        process_page(&spec, &root, page, &ctx, dry_run).await
+                    }
+                    Err(e) => {
+                        tracing::error!(resource = %spec.name, page, error = %e, "failed to fetch page");
+                        (0, page)
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for result in futures::future::join_all(tasks).await {
+        match result {
+            Ok((page_processed, page)) => {
+                processed += page_processed;
+                tracing::info!(
+                    resource = %spec.name,
+                    processed,
+                    total,
+                    page,
+                    total_pages,
+                    "processed page"
+                );
+                if let Some(sink) = &progress {
+                    sink.update(processed, total);
+                }
+            }
+            Err(e) => tracing::error!(resource = %spec.name, error = %e, "page task panicked"),
+        }
+    }
+
+    if let Some(sink) = &progress {
+        sink.finish();
+    }
+}
+
+#[tracing::instrument(skip(root, ctx, spec), fields(page, resource = %spec.name))]
+async fn process_page(
+    spec: &ResourceSpec,
+    root: &Root,
+    page: i32,
+    ctx: &DeleteContext<'_>,
+    dry_run: bool,
+) -> (i32, i32) {
+    let mut processed = 0;
+
+    for data in &root.data {
+        let linked_id = linked_shopify_id(spec, data);
+
+        if dry_run {
+            tracing::info!(
+                id = data.id,
+                linked_id = ?linked_id,
+                target = "strapi",
+                outcome = "dry_run",
+                "would delete entry"
+            );
+            processed += 1;
+            continue;
+        }
+
+        if let (Some(shopify_resource), Some(linked_id)) = (&spec.shopify_resource, &linked_id) {
+            match delete_shopify_resource(
+                ctx.shopify_config,
+                shopify_resource,
+                linked_id,
+                ctx.semaphore,
+                ctx.max_retries,
+            )
+            .await
+            {
+                Ok(()) => tracing::info!(
+                    id = data.id,
+                    linked_id = %linked_id,
+                    target = "shopify",
+                    outcome = "deleted",
+                    "deleted entry"
+                ),
+                Err(e) => tracing::warn!(
+                    id = data.id,
+                    linked_id = %linked_id,
+                    target = "shopify",
+                    outcome = "failed",
+                    error = %e,
+                    "failed to delete entry"
+                ),
+            }
+        }
+
+        match delete_strapi_resource(
+            ctx.strapi_config,
+            &spec.strapi_collection,
+            data.id,
+            ctx.semaphore,
+            ctx.max_retries,
+        )
+        .await
+        {
+            Ok(()) => tracing::info!(
+                id = data.id,
+                linked_id = ?linked_id,
+                target = "strapi",
+                outcome = "deleted",
+                "deleted entry"
+            ),
+            Err(e) => tracing::warn!(
+                id = data.id,
+                linked_id = ?linked_id,
+                target = "strapi",
+                outcome = "failed",
+                error = %e,
+                "failed to delete entry"
+            ),
+        }
+
+        processed += 1;
+    }
+
+    (processed, page)
+}
+
+async fn delete_shopify_resource(
+    config: &ShopifyConfig,
+    resource: &str,
+    res_id: &str,
+    semaphore: &Semaphore,
+    max_retries: u32,
+) -> Result<(), DeleteError> {
+    let url = format!("{}/{}/{}.json", config.shop_url, resource, res_id);
+    let client = reqwest::Client::new();
+
+    call_with_retry(semaphore, max_retries, || {
+        let client = client.clone();
+        let url = url.clone();
+        async move {
+            let access_token = config.token_source.current_token().await?;
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert("Content-Type", "application/json".parse().unwrap());
+            headers.insert("X-Shopify-Access-Token", access_token.parse().unwrap());
+            client.delete(&url).headers(headers).send().await
+        }
+    })
+    .await
+}
+
+async fn delete_strapi_resource(
+    config: &StrapiConfig,
+    collection: &str,
+    res_id: i32,
+    semaphore: &Semaphore,
+    max_retries: u32,
+) -> Result<(), DeleteError> {
+    let url = format!("{}/{}/{}", config.base_url, collection, res_id);
+    let client = reqwest::Client::new();
+
+    call_with_retry(semaphore, max_retries, || {
+        client
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", config.auth_token))
+            .send()
+    })
+    .await
+}
@@ -1,7 +1,32 @@
-use clap::Parser;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::{Parser, Subcommand};
 use dotenv::dotenv;
 use serde::{Deserialize, Serialize};
 
+mod auth;
+mod backup;
+mod engine;
+mod resource;
+mod retry;
+mod service;
+mod telemetry;
+
+use auth::ShopifyTokenSource;
+
+/// Receives progress of a deletion job from [`engine::run_job`]. The
+/// service's job table is the only implementation, and both `serve` and the
+/// `--delete` CLI path read it back via `delete_status`.
+pub trait ProgressSink: Send + Sync {
+    fn update(&self, processed: i32, total: i32);
+    fn finish(&self);
+    /// Marks the job as terminated by an error instead of completing
+    /// normally, so pollers of `delete_status` don't wait forever.
+    fn fail(&self, message: &str);
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Pagination {
     pub page: i32,
@@ -20,13 +45,7 @@ pub struct Meta {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DataElement {
     pub id: i32,
-    pub attributes: DataAtribute,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DataAtribute {
-    #[serde(rename = "cartReference")]
-    pub cart_reference: Option<String>,
+    pub attributes: serde_json::Value,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -35,17 +54,60 @@ pub struct Root {
     pub meta: Meta,
 }
 
-#[derive(Debug)]
 struct ShopifyConfig {
-    access_token: String,
+    token_source: ShopifyTokenSource,
     shop_url: String,
 }
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Cli {
+    /// Name of the resource to delete, as configured in `resources.toml`.
     #[arg(long)]
-    delete: String,
+    delete: Option<String>,
+
+    /// Log what would be deleted without issuing any DELETE requests.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Maximum number of delete requests in flight at once.
+    #[arg(long, default_value_t = 5)]
+    concurrency: usize,
+
+    /// Maximum retry attempts for a single delete before giving up on it.
+    #[arg(long, default_value_t = 5)]
+    max_retries: u32,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Stream every entry of a resource as newline-delimited JSON, to back up before deleting.
+    Export {
+        /// Resource name, as configured in `resources.toml`.
+        #[arg(long, default_value = "orders")]
+        resource: String,
+        /// File to write NDJSON to; defaults to stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Restore a resource's entries into Strapi from NDJSON produced by `export`.
+    Import {
+        /// Resource name, as configured in `resources.toml`.
+        #[arg(long, default_value = "orders")]
+        resource: String,
+        /// File to read NDJSON from; defaults to stdin.
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Run as a long-lived tarpc service exposing delete_orders/delete_customers/delete_status.
+    Serve {
+        /// Address to bind the tarpc listener on.
+        #[arg(long, default_value = "127.0.0.1:7000")]
+        addr: SocketAddr,
+    },
 }
 
 #[derive(Debug)]
@@ -57,25 +119,47 @@ struct StrapiConfig {
 #[tokio::main]
 async fn main() {
     dotenv().ok();
+    telemetry::init();
 
     let cli = Cli::parse();
 
-    match cli.delete.as_str() {
-        "orders" => delete_orders().await,
-        "customers" => delete_customers().await,
-        _ => println!("Invalid command"),
+    match cli.command {
+        Some(Command::Export { resource, output }) => {
+            if let Err(e) = backup::export_resource(&resource, output).await {
+                println!("Error: {}", e);
+            }
+        }
+        Some(Command::Import { resource, input }) => {
+            backup::import_resource(&resource, input).await
+        }
+        Some(Command::Serve { addr }) => {
+            if let Err(e) = service::serve(addr).await {
+                println!("Error: {}", e);
+            }
+        }
+        None => match cli.delete.as_deref() {
+            Some(resource) => {
+                let specs = resource::load_specs(std::path::Path::new(resource::DEFAULT_CONFIG_PATH));
+                if resource::find(&specs, resource).is_none() {
+                    println!("Error: no resource spec configured for '{}'", resource);
+                    return;
+                }
+                service::run_local(resource, cli.concurrency, cli.max_retries, cli.dry_run).await
+            }
+            None => println!("Invalid command"),
+        },
     }
 }
 
-fn load_configs() -> (ShopifyConfig, StrapiConfig) {
+async fn load_configs() -> (ShopifyConfig, StrapiConfig) {
     let strapi_url = std::env::var("STRAPI_BASE_URL").expect("STRAPI_BASE_URL not set");
     let strapi_token = std::env::var("STRAPI_TOKEN").expect("STRAPI_TOKEN not set");
-    let shopify_token = std::env::var("SHOP_ACCESS_TOKEN").expect("SHOP_ACCESS_TOKEN not set");
     let shopify_url = std::env::var("SHOP_BASE_URL").expect("SHOP_BASE_URL not set");
+    let token_source = ShopifyTokenSource::from_env(&shopify_url).await;
 
     (
         ShopifyConfig {
-            access_token: shopify_token,
+            token_source,
             shop_url: shopify_url,
         },
         StrapiConfig {
@@ -85,140 +169,25 @@ fn load_configs() -> (ShopifyConfig, StrapiConfig) {
     )
 }
 
-async fn delete_orders() {
-    let res = fetch_root_for_page(1).await;
-    //TODO: Replace this with fetch meta
-    match res {
-        Ok(root) => process_root_orders(root).await, //need to give it meta
-        Err(e) => println!("Error: {}", e),
-    }
-}
-
-async fn delete_customers() {
-    load_configs();
-    println!("Deleting customers");
-}
-
-fn create_order_filter(page: i32, page_size: i32) -> String {
-    format!("fields[0]=id&fields[1]=cartReference&pagination[pageSize]={}&pagination[page]={}&publicationState=preview&locale[0]=en", page_size, page)
-}
-
-async fn process_root_orders(root: Root) {
-    //1. Get total pages
-    let total_pages = root.meta.pagination.page_count;
-    let total_orders = root.meta.pagination.total;
-    let mut processed_orders = 0;
-    println!("About to start deleting: {}", total_orders);
-
-    //2. Loop through pages
-    let tasks: Vec<_> = (1..=total_pages)
-        .into_iter()
-        .map(|page| {
-            tokio::spawn(async move {
-                let new_root = fetch_root_for_page(page).await;
-                match new_root {
-                    Ok(next) => process_paged_orders(&next, page).await,
-                    Err(e) => {
-                        println!("Error: {}", e);
-                        (0, page)
-                    }
-                }
-            })
-        })
-        .collect();
-
-    let results = futures::future::join_all(tasks).await;
-
-    for result in results {
-        match result {
-            Ok(result_values) => {
-                let (total_processed_orders, result_page) = result_values;
-                processed_orders += total_processed_orders;
-                println!(
-                    "Processed {} of {} 🧾 Orders (Page -> {} of {})",
-                    processed_orders, total_orders, result_page, total_pages
-                );
-            }
-            Err(e) => println!("Error: {}", e),
+/// Runs the deletion job configured under `resource_name` in
+/// `resources.toml`, via the generic engine.
+async fn delete_resource(
+    resource_name: &str,
+    concurrency: usize,
+    max_retries: u32,
+    dry_run: bool,
+    progress: Option<Arc<dyn ProgressSink>>,
+) {
+    let specs = resource::load_specs(std::path::Path::new(resource::DEFAULT_CONFIG_PATH));
+    match resource::find(&specs, resource_name) {
+        Some(spec) => {
+            engine::run_job(spec.clone(), concurrency, max_retries, dry_run, progress).await
         }
-    }
-}
-
-async fn fetch_root_for_page(page: i32) -> Result<Root, reqwest::Error> {
-    let strapi_orders_url: String = format!(
-        "{}/orders",
-        std::env::var("STRAPI_BASE_URL").unwrap().as_str()
-    );
-    let strapi_token: String = std::env::var("STRAPI_TOKEN").unwrap();
-
-    let order_filters = create_order_filter(page, 10);
-    let client = reqwest::Client::new();
-    let header = format!("Bearer {}", strapi_token);
-    let url = format!("{}?{}", strapi_orders_url, order_filters);
-    //add headers
-    let res = client
-        .get(&url)
-        .header("Accept", "application/json")
-        .header("Authorization", &header)
-        .send()
-        .await?
-        .json::<Root>()
-        .await;
-
-    return res;
-}
-
-async fn process_paged_orders(root: &Root, page: i32) -> (i32, i32) {
-    //process and handle exceptions per order
-    let (shopify_config, strapi_config) = load_configs();
-    let mut processed: i32 = 0;
-    for data in &root.data {
-        print!(".");
-        if data.attributes.cart_reference.is_some() {
-            delete_shopify_resource(
-                &shopify_config,
-                "orders",
-                data.attributes.cart_reference.as_ref().unwrap().to_string(),
-            )
-            .await;
+        None => {
+            tracing::error!(resource = resource_name, "no resource spec configured");
+            if let Some(sink) = &progress {
+                sink.fail(&format!("no resource spec configured for '{}'", resource_name));
+            }
         }
-        delete_strapi_resource(&strapi_config, "order", data.id).await;
-        processed += 1;
-    }
-    (processed, page)
-}
-
-async fn delete_shopify_resource(config: &ShopifyConfig, resource: &str, res_id: String) -> bool {
-    let url = format!("{}/{}/{}.json", config.shop_url, resource, res_id);
-
-    let client = reqwest::Client::new();
-    let mut headers = reqwest::header::HeaderMap::new();
-    headers.insert("Content-Type", "application/json".parse().unwrap());
-    headers.insert(
-        "X-Shopify-Access-Token",
-        config.access_token.parse().unwrap(),
-    );
-
-    let response = client.delete(&url).headers(headers).send().await;
-
-    match response {
-        Ok(_) => true,
-        Err(_) => false,
-    }
-}
-
-async fn delete_strapi_resource(config: &StrapiConfig, resource: &str, res_id: i32) -> bool {
-    let url = format!("{}/{}/{}", config.base_url, resource, res_id);
-
-    let client = reqwest::Client::new();
-    let response = client
-        .delete(&url)
-        .header("Authorization", format!("Bearer {}", config.auth_token))
-        .send()
-        .await;
-
-    match response {
-        Ok(_) => true,
-        Err(_) => false,
     }
 }
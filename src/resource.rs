@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// Default location of the resource-spec config file, relative to the
+/// working directory the binary is run from.
+pub const DEFAULT_CONFIG_PATH: &str = "resources.toml";
+
+/// Declares one deletable resource type: where to list it in Strapi, which
+/// fields to fetch, and (optionally) the linked Shopify resource to delete
+/// alongside it. Lets new resource types be added declaratively instead of
+/// hardwiring another branch into the dispatch `match` in `main`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResourceSpec {
+    /// Name used to select this spec, e.g. from `--delete <name>`.
+    pub name: String,
+    /// Strapi collection to list and delete from, e.g. `"orders"`.
+    pub strapi_collection: String,
+    /// Fields to request from Strapi for each entry (always include `"id"`).
+    pub strapi_fields: Vec<String>,
+    /// Shopify resource to delete alongside the Strapi entry, if any, e.g. `"orders"`.
+    pub shopify_resource: Option<String>,
+    /// Strapi attribute that holds the linked Shopify resource's id.
+    pub link_field: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResourceSpecFile {
+    resource: Vec<ResourceSpec>,
+}
+
+/// Loads every configured [`ResourceSpec`] from a TOML file.
+pub fn load_specs(path: &Path) -> Vec<ResourceSpec> {
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("failed to read resource config {}: {}", path.display(), e));
+    let file: ResourceSpecFile = toml::from_str(&contents)
+        .unwrap_or_else(|e| panic!("failed to parse resource config {}: {}", path.display(), e));
+    file.resource
+}
+
+/// Finds the spec with the given `name` among `specs`.
+pub fn find<'a>(specs: &'a [ResourceSpec], name: &str) -> Option<&'a ResourceSpec> {
+    specs.iter().find(|spec| spec.name == name)
+}
@@ -0,0 +1,143 @@
+use std::fmt;
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::StatusCode;
+
+/// Base delay for the exponential backoff loop in [`call_with_retry`].
+const BASE_DELAY_MS: u64 = 500;
+/// Upper bound on any single backoff sleep.
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Why a delete call failed for good: either it exhausted its retry budget,
+/// or it got a response that is not retried at all.
+#[derive(Debug)]
+pub enum DeleteError {
+    /// The upstream kept responding 429 past `max_retries`.
+    RateLimited,
+    /// The upstream kept responding with a 5xx past `max_retries`.
+    ServerError(StatusCode),
+    /// The request never got a response (DNS, connect, timeout, ...).
+    Transport(reqwest::Error),
+    /// Any other non-success status that isn't worth retrying (e.g. 4xx).
+    Rejected(StatusCode),
+}
+
+impl fmt::Display for DeleteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeleteError::RateLimited => write!(f, "gave up after repeated 429s"),
+            DeleteError::ServerError(status) => {
+                write!(f, "gave up after repeated server errors ({})", status)
+            }
+            DeleteError::Transport(e) => write!(f, "transport error: {}", e),
+            DeleteError::Rejected(status) => write!(f, "request rejected: {}", status),
+        }
+    }
+}
+
+impl std::error::Error for DeleteError {}
+
+/// Runs `attempt` behind `semaphore` (bounding in-flight requests), retrying
+/// on HTTP 429 and 5xx/transport errors.
+///
+/// - On 429, sleeps for exactly the `Retry-After` header (seconds), falling
+///   back to 1s if the header is missing or unparsable.
+/// - On 5xx or a transport error, sleeps `rand(0, min(cap, base * 2^attempt))`
+///   (exponential backoff with full jitter) before trying again.
+///
+/// Gives up after `max_retries` retries (`max_retries + 1` attempts in all),
+/// returning a [`DeleteError`] describing why.
+pub async fn call_with_retry<F, Fut>(
+    semaphore: &tokio::sync::Semaphore,
+    max_retries: u32,
+    mut attempt: F,
+) -> Result<(), DeleteError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<reqwest::Response, reqwest::Error>>,
+{
+    let _permit = semaphore.acquire().await.expect("semaphore closed");
+
+    for attempt_no in 0..=max_retries {
+        let last_attempt = attempt_no == max_retries;
+
+        match attempt().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+
+                if status == StatusCode::TOO_MANY_REQUESTS {
+                    if last_attempt {
+                        return Err(DeleteError::RateLimited);
+                    }
+                    sleep_retry_after(&response).await;
+                    continue;
+                }
+
+                if status.is_server_error() {
+                    if last_attempt {
+                        return Err(DeleteError::ServerError(status));
+                    }
+                    sleep_backoff(attempt_no).await;
+                    continue;
+                }
+
+                return Err(DeleteError::Rejected(status));
+            }
+            Err(e) => {
+                if last_attempt {
+                    return Err(DeleteError::Transport(e));
+                }
+                sleep_backoff(attempt_no).await;
+            }
+        }
+    }
+
+    unreachable!("loop always returns on its last attempt")
+}
+
+async fn sleep_retry_after(response: &reqwest::Response) {
+    let seconds = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(1);
+    tokio::time::sleep(Duration::from_secs(seconds)).await;
+}
+
+/// Upper bound of the jittered sleep for `attempt_no`: `base * 2^attempt`,
+/// capped at `MAX_DELAY`. Split out from [`sleep_backoff`] so the bound
+/// itself can be asserted on without actually sleeping.
+fn max_backoff_delay_ms(attempt_no: u32) -> u64 {
+    let cap_ms = MAX_DELAY.as_millis() as u64;
+    BASE_DELAY_MS
+        .saturating_mul(1u64 << attempt_no.min(20))
+        .min(cap_ms)
+}
+
+async fn sleep_backoff(attempt_no: u32) {
+    let max_delay_ms = max_backoff_delay_ms(attempt_no);
+    let jittered_ms = rand::thread_rng().gen_range(0..=max_delay_ms);
+    tokio::time::sleep(Duration::from_millis(jittered_ms)).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_backoff_delay_starts_at_base_delay() {
+        assert_eq!(max_backoff_delay_ms(0), BASE_DELAY_MS);
+        assert_eq!(max_backoff_delay_ms(1), BASE_DELAY_MS * 2);
+        assert_eq!(max_backoff_delay_ms(2), BASE_DELAY_MS * 4);
+    }
+
+    #[test]
+    fn max_backoff_delay_caps_at_max_delay() {
+        let cap_ms = MAX_DELAY.as_millis() as u64;
+        assert_eq!(max_backoff_delay_ms(10), cap_ms);
+        assert_eq!(max_backoff_delay_ms(u32::MAX), cap_ms);
+    }
+}
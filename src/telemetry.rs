@@ -0,0 +1,31 @@
+use std::env;
+
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Registry};
+
+/// Installs the process-wide `tracing` subscriber: always a stdout layer,
+/// plus an OpenTelemetry/Jaeger layer when `OTEL_EXPORTER_JAEGER_ENDPOINT`
+/// is set so a delete run can be inspected as a distributed trace instead
+/// of a wall of dots.
+pub fn init() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let fmt_layer = tracing_subscriber::fmt::layer().with_target(false);
+    let registry = Registry::default().with(filter).with(fmt_layer);
+
+    match env::var("OTEL_EXPORTER_JAEGER_ENDPOINT") {
+        Ok(endpoint) => {
+            let tracer = opentelemetry_jaeger::new_collector_pipeline()
+                .with_endpoint(endpoint)
+                .with_reqwest()
+                .with_service_name("delete-strapi-orders")
+                .install_batch(opentelemetry_sdk::runtime::Tokio)
+                .expect("failed to install Jaeger pipeline");
+
+            registry
+                .with(tracing_opentelemetry::layer().with_tracer(tracer))
+                .init();
+        }
+        Err(_) => registry.init(),
+    }
+}